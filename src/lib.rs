@@ -1,61 +1,159 @@
+#![feature(ptr_metadata, unsize)]
+
+use allocator_api2::alloc::{Allocator, Global};
 use std::alloc;
-use std::ops::{Deref, DerefMut};
+use std::marker::Unsize;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::ptr::{self, Pointee};
 use std::{marker::PhantomData, ptr::NonNull};
 
-pub struct MyVec<T> {
+pub struct MyVec<T, A: Allocator = Global> {
     /// 1. NonNull<T> will never be Null
     /// 2. NonNull<T> is covariant over T
     ptr: NonNull<T>,
     cap: usize,
     len: usize,
+    /// The allocator backing `ptr`; also used to free it on drop.
+    alloc: A,
     /// Pretending to own T for dropck later
     _marker: PhantomData<T>,
 }
 
-unsafe impl<T: Sync> Sync for MyVec<T> {}
-unsafe impl<T: Send> Send for MyVec<T> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for MyVec<T, A> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for MyVec<T, A> {}
 
-impl<T> MyVec<T> {
+impl<T> MyVec<T, Global> {
     pub fn new() -> Self {
-        assert!(
-            std::mem::align_of::<T>() != 0,
-            "Zero-Sized-Types are not allowed to create Vec"
-        );
+        Self::new_in(Global)
+    }
+
+    /// Creates a vector with room for at least `cap` elements in a single
+    /// up-front allocation.
+    pub fn with_capacity(cap: usize) -> Self {
+        let mut v = Self::new_in(Global);
+        // ZSTs already report `usize::MAX` capacity and never allocate.
+        if std::mem::size_of::<T>() != 0 && cap > 0 {
+            v.grow_to(cap);
+        }
+        v
+    }
+
+    /// Reconstructs a vector from its raw components.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from a previous [`MyVec::into_raw_parts`] on the global
+    /// allocator (or be a matching `alloc`-allocated, non-null pointer), with
+    /// `len <= cap` and `cap` describing the real allocation size.
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, cap: usize) -> Self {
+        MyVec {
+            ptr: NonNull::new_unchecked(ptr),
+            cap,
+            len,
+            alloc: Global,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Decomposes the vector into its raw `(ptr, len, cap)` parts without
+    /// running its destructor; the caller takes over freeing the allocation.
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let me = std::mem::ManuallyDrop::new(self);
+        (me.ptr.as_ptr(), me.len, me.cap)
+    }
+}
+
+impl<T, A: Allocator> MyVec<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        // For ZSTs we never allocate, so pretend we have infinite capacity and
+        // let `grow` stay unreachable (see the assert in `try_grow_to`).
+        let cap = if std::mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            0
+        };
         MyVec {
             ptr: NonNull::dangling(),
-            cap: 0,
+            cap,
             len: 0,
+            alloc,
             _marker: PhantomData,
         }
     }
 
     fn grow(&mut self) {
+        // Amortized growth doubles capacity; the real work lives in `grow_to`.
         let new_cap = if self.cap == 0 { 1 } else { self.cap * 2 };
-        let new_layout = alloc::Layout::array::<T>(new_cap).unwrap();
+        self.grow_to(new_cap);
+    }
+
+    fn grow_to(&mut self, min_cap: usize) {
+        // The infallible path is just the fallible one with allocator failures
+        // routed through `handle_alloc_error`, matching the previous behavior.
+        if let Err(e) = self.try_grow_to(min_cap) {
+            match e {
+                TryReserveError::CapacityOverflow => panic!("capacity overflow"),
+                TryReserveError::AllocError { layout } => alloc::handle_alloc_error(layout),
+            }
+        }
+    }
+
+    fn try_grow(&mut self) -> Result<(), TryReserveError> {
+        let new_cap = if self.cap == 0 { 1 } else { self.cap * 2 };
+        self.try_grow_to(new_cap)
+    }
+
+    fn try_grow_to(&mut self, min_cap: usize) -> Result<(), TryReserveError> {
+        if min_cap <= self.cap {
+            return Ok(());
+        }
+
+        // ZSTs set `cap` to `usize::MAX` in `new`, so getting past the early-out
+        // above means a real element type has overflowed its capacity.
+        assert!(std::mem::size_of::<T>() != 0, "capacity overflow");
+
+        let new_cap = min_cap;
+        let new_layout =
+            alloc::Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
 
         // ptr::offset takes an `isize` parameter which is the max number of units of T a pointer
         // can possibly reach
-        assert!(
-            new_layout.size() <= isize::MAX as usize,
-            "Allocation too large"
-        );
+        if new_layout.size() > isize::MAX as usize {
+            return Err(TryReserveError::CapacityOverflow);
+        }
 
         let new_ptr = if self.cap == 0 {
-            unsafe { alloc::alloc(new_layout) }
+            self.alloc.allocate(new_layout)
         } else {
             let old_layout = alloc::Layout::array::<T>(self.cap).unwrap();
-            let old_ptr = self.ptr.as_ptr() as *mut u8;
-            unsafe { alloc::realloc(old_ptr, old_layout, new_cap) }
+            let old_ptr = self.ptr.cast();
+            unsafe { self.alloc.grow(old_ptr, old_layout, new_layout) }
         };
 
-        // if allocation failed, None will be returned
-        self.ptr = match NonNull::new(new_ptr as *mut T) {
-            Some(p) => p,
-            None => {
-                alloc::handle_alloc_error(new_layout);
-            }
+        // an allocator failure is surfaced as an error rather than aborting
+        self.ptr = match new_ptr {
+            Ok(p) => p.cast(),
+            Err(_) => return Err(TryReserveError::AllocError { layout: new_layout }),
         };
         self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more elements in a single
+    /// allocation, growing to `len + additional` rather than doubling.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed > self.cap {
+            self.grow_to(needed);
+        }
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let needed = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        self.try_grow_to(needed)
     }
 
     pub fn push(&mut self, ele: T) {
@@ -69,6 +167,17 @@ impl<T> MyVec<T> {
         self.len += 1;
     }
 
+    pub fn try_push(&mut self, ele: T) -> Result<(), T> {
+        if self.len == self.cap && self.try_grow().is_err() {
+            return Err(ele);
+        }
+        unsafe {
+            std::ptr::write(self.ptr.as_ptr().add(self.len), ele);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             None
@@ -106,30 +215,51 @@ impl<T> MyVec<T> {
         }
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        let ptr = self.ptr;
-        let len = self.len;
-        let cap = self.cap;
-
+    pub fn into_iter(self) -> IntoIter<T, A> {
         unsafe {
+            let iter = RawValIter::new(&self);
+            let buf = self.ptr;
+            let cap = self.cap;
+            // move the allocator out before forgetting `self` so `IntoIter` can
+            // free the buffer later
+            let alloc = std::ptr::read(&self.alloc);
             // take ownership of self without running its destructor
             std::mem::forget(self);
             IntoIter {
-                buf: ptr,
-                cap: cap,
-                start: ptr.as_ptr(),
-                end: if cap == 0 {
-                    ptr.as_ptr()
-                } else {
-                    ptr.as_ptr().add(len)
-                },
+                buf,
+                cap,
+                alloc,
+                iter,
                 _marker: PhantomData,
             }
         }
     }
+
+    pub fn drain(&mut self) -> Drain<'_, T, A> {
+        let iter = unsafe { RawValIter::new(self) };
+
+        // Set the length to zero up front so that a `mem::forget`-leaked
+        // `Drain` leaves the vec empty rather than double-dropping elements.
+        self.len = 0;
+
+        Drain {
+            iter,
+            vec: PhantomData,
+        }
+    }
 }
 
-impl<T> Deref for MyVec<T> {
+/// The error returned by the fallible allocation methods ([`MyVec::try_reserve`]
+/// and [`MyVec::try_push`]) when a request cannot be satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds what the layout or `isize::MAX` allows.
+    CapacityOverflow,
+    /// The allocator failed to supply memory for the given layout.
+    AllocError { layout: alloc::Layout },
+}
+
+impl<T, A: Allocator> Deref for MyVec<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
@@ -137,54 +267,79 @@ impl<T> Deref for MyVec<T> {
     }
 }
 
-impl<T> DerefMut for MyVec<T> {
+impl<T, A: Allocator> DerefMut for MyVec<T, A> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
     }
 }
 
-impl<T> Drop for MyVec<T> {
+impl<T, A: Allocator> Drop for MyVec<T, A> {
     fn drop(&mut self) {
-        // if self.cap == 0, nothing has been allocated
-        if self.cap != 0 {
-            // this could be removed when T:!Drop as in the elements don't need to be dropped
-            while let Some(_) = self.pop() {}
+        // Always drop the elements (ZSTs may have side-effecting destructors),
+        // then free the buffer only when a real allocation happened; ZSTs never
+        // allocate despite their `usize::MAX` capacity.
+        while let Some(_) = self.pop() {}
+        if self.cap != 0 && std::mem::size_of::<T>() != 0 {
+            let layout = alloc::Layout::array::<T>(self.cap).unwrap();
             unsafe {
-                std::alloc::dealloc(
-                    self.ptr.as_ptr() as *mut u8,
-                    alloc::Layout::array::<T>(self.cap).unwrap(),
-                )
+                self.alloc.deallocate(self.ptr.cast(), layout);
             }
         }
     }
 }
 
-pub struct IntoIter<T> {
-    buf: NonNull<T>,
-    cap: usize,
+/// Shared pointer-walking logic behind both `IntoIter` and `Drain`. It yields
+/// elements by value but is unaware of the backing allocation, so each wrapper
+/// is responsible for freeing (or keeping) the buffer.
+struct RawValIter<T> {
     start: *const T,
     end: *const T,
-    _marker: PhantomData<T>,
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T> RawValIter<T> {
+    /// # Safety
+    ///
+    /// The referenced slice must outlive the returned `RawValIter`, which ties
+    /// no lifetime to it. The caller must also make sure the elements are not
+    /// dropped through any other path while the iterator is live.
+    unsafe fn new(slice: &[T]) -> Self {
+        RawValIter {
+            start: slice.as_ptr(),
+            end: if std::mem::size_of::<T>() == 0 {
+                // Walk the pointer as a plain counter for ZSTs.
+                (slice.as_ptr() as usize + slice.len()) as *const T
+            } else if slice.is_empty() {
+                slice.as_ptr()
+            } else {
+                slice.as_ptr().add(slice.len())
+            },
+        }
+    }
+}
+
+impl<T> Iterator for RawValIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             None
+        } else if std::mem::size_of::<T>() == 0 {
+            self.start = (self.start as usize + 1) as *const T;
+            Some(unsafe { std::ptr::read(NonNull::<T>::dangling().as_ptr()) })
         } else {
             self.start = unsafe { self.start.add(1) };
             Some(unsafe { std::ptr::read(self.start.sub(1)) })
         }
     }
-
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T> DoubleEndedIterator for RawValIter<T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             None
+        } else if std::mem::size_of::<T>() == 0 {
+            self.end = (self.end as usize - 1) as *const T;
+            Some(unsafe { std::ptr::read(NonNull::<T>::dangling().as_ptr()) })
         } else {
             self.end = unsafe { self.end.sub(1) };
             Some(unsafe { std::ptr::read(self.end) })
@@ -192,32 +347,266 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
-impl<T> Drop for IntoIter<T> {
+pub struct IntoIter<T, A: Allocator = Global> {
+    buf: NonNull<T>,
+    cap: usize,
+    alloc: A,
+    iter: RawValIter<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
-        if self.cap != 0 {
-            for _ in &mut *self {}
+        // Drop any remaining elements (including ZSTs, whose destructors still
+        // matter) but only free the buffer when a real allocation happened.
+        for _ in &mut *self {}
+        if self.cap != 0 && std::mem::size_of::<T>() != 0 {
             let layout = alloc::Layout::array::<T>(self.cap).unwrap();
             unsafe {
-                alloc::dealloc(self.buf.as_ptr() as *mut u8, layout);
+                self.alloc.deallocate(self.buf.cast(), layout);
             }
+        }
+    }
+}
 
+/// Removes and yields every element of a `MyVec` while leaving its
+/// allocation in place so it stays reusable afterwards.
+pub struct Drain<'a, T: 'a, A: Allocator + 'a = Global> {
+    vec: PhantomData<&'a mut MyVec<T, A>>,
+    iter: RawValIter<T>,
+}
+
+impl<T, A: Allocator> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, A: Allocator> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        // Exhaust the iterator to drop any un-yielded elements. The buffer is
+        // owned by the `MyVec` and deliberately left untouched.
+        for _ in &mut *self {}
+    }
+}
+
+/// A vector of `?Sized` values stored inline.
+///
+/// Unlike `MyVec<Box<T>>`, `DynVec<T>` keeps every element packed in a single
+/// byte buffer and remembers each one's offset and pointer metadata in a
+/// parallel `MyVec`, reconstructing `&T`/`&mut T` fat pointers on demand.
+pub struct DynVec<T: ?Sized, A: Allocator = Global> {
+    /// Packed, densely stored element bytes.
+    buf: NonNull<u8>,
+    /// Allocated capacity of `buf`, in bytes.
+    cap: usize,
+    /// Used bytes of `buf` (the bump offset for the next element).
+    len: usize,
+    /// Current alignment of `buf`; grows to the strictest element seen.
+    align: usize,
+    /// `(offset, metadata)` for each stored element, in insertion order.
+    entries: MyVec<(usize, <T as Pointee>::Metadata)>,
+    /// The allocator backing `buf`; also used to free it on drop.
+    alloc: A,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ?Sized> DynVec<T, Global> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> DynVec<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        DynVec {
+            buf: NonNull::dangling(),
+            cap: 0,
+            len: 0,
+            align: 1,
+            entries: MyVec::new(),
+            alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of elements stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Appends `val`, coercing it to the unsized element type and copying its
+    /// bytes into the packed buffer.
+    pub fn push_unsized<U: Unsize<T>>(&mut self, val: U) {
+        let coerced: &T = &val;
+        let meta = ptr::metadata(coerced as *const T);
+        let layout = alloc::Layout::for_value(coerced);
+
+        // Bump to a correctly aligned slot for this element.
+        let offset = align_up(self.len, layout.align());
+        let end = offset + layout.size();
+        self.grow_to(end, layout.align());
+
+        unsafe {
+            let dst = self.buf.as_ptr().add(offset) as *mut U;
+            ptr::write(dst, val);
+        }
+
+        self.len = end;
+        self.entries.push((offset, meta));
+    }
+
+    /// Grows `buf` to hold at least `needed` bytes with at least `align`
+    /// alignment, doubling like `MyVec::grow`. The `Allocator::grow` call moves
+    /// the existing bytes itself when the alignment tightens.
+    fn grow_to(&mut self, needed: usize, align: usize) {
+        let new_align = self.align.max(align);
+        if needed <= self.cap && new_align == self.align {
+            return;
+        }
+
+        let new_cap = needed.max(if self.cap == 0 { 1 } else { self.cap * 2 });
+        let new_layout = alloc::Layout::from_size_align(new_cap, new_align).unwrap();
+        assert!(
+            new_layout.size() <= isize::MAX as usize,
+            "Allocation too large"
+        );
+
+        // `new_cap` is always strictly larger than `self.cap` here, so the
+        // `Allocator::grow` size precondition holds even on a pure realignment.
+        let new_ptr = if self.cap == 0 {
+            self.alloc.allocate(new_layout)
+        } else {
+            let old_layout = alloc::Layout::from_size_align(self.cap, self.align).unwrap();
+            unsafe { self.alloc.grow(self.buf, old_layout, new_layout) }
+        };
+
+        self.buf = match new_ptr {
+            Ok(p) => p.cast(),
+            Err(_) => alloc::handle_alloc_error(new_layout),
+        };
+        self.cap = new_cap;
+        self.align = new_align;
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Index<usize> for DynVec<T, A> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        let (offset, meta) = self.entries[idx];
+        unsafe {
+            let data = self.buf.as_ptr().add(offset) as *const ();
+            &*ptr::from_raw_parts(data, meta)
         }
     }
 }
 
+impl<T: ?Sized, A: Allocator> IndexMut<usize> for DynVec<T, A> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        let (offset, meta) = self.entries[idx];
+        unsafe {
+            let data = self.buf.as_ptr().add(offset) as *mut ();
+            &mut *ptr::from_raw_parts_mut(data, meta)
+        }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for DynVec<T, A> {
+    fn drop(&mut self) {
+        // Drop each element through its reconstructed fat pointer before the
+        // backing buffer goes away.
+        for &(offset, meta) in self.entries.iter() {
+            unsafe {
+                let data = self.buf.as_ptr().add(offset) as *mut ();
+                let ptr: *mut T = ptr::from_raw_parts_mut(data, meta);
+                ptr::drop_in_place(ptr);
+            }
+        }
+        if self.cap != 0 {
+            let layout = alloc::Layout::from_size_align(self.cap, self.align).unwrap();
+            unsafe {
+                self.alloc.deallocate(self.buf, layout);
+            }
+        }
+    }
+}
+
+/// Rounds `offset` up to the next multiple of `align` (a power of two).
+fn align_up(offset: usize, align: usize) -> usize {
+    (offset + align - 1) & !(align - 1)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::MyVec;
+    use super::{DynVec, MyVec};
     #[test]
     fn create_new_success() {
         let v: MyVec<i32> = MyVec::new();
         assert!(std::mem::size_of_val(&v) != 0);
     }
     #[test]
-    #[should_panic]
-    fn create_new_fail() {
-        let v: MyVec<()> = MyVec::new();
-        assert!(std::mem::size_of_val(&v) == 0);
+    fn zst_push_pop_and_iter() {
+        let mut v: MyVec<()> = MyVec::new();
+        v.push(());
+        v.push(());
+        v.push(());
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.pop(), Some(()));
+        assert_eq!(v.len(), 2);
+
+        let mut it = v.into_iter();
+        assert_eq!(it.next(), Some(()));
+        assert_eq!(it.next_back(), Some(()));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn zst_drop_runs_destructors() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Zst;
+        impl Drop for Zst {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        {
+            let mut v: MyVec<Zst> = MyVec::new();
+            v.push(Zst);
+            v.push(Zst);
+            v.push(Zst);
+            // dropping the vec directly must run every element's destructor
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
     }
 
     #[test]
@@ -327,4 +716,173 @@ mod tests {
         assert_eq!(it.next_back(), None);
 
     }
+
+    #[test]
+    fn test_with_capacity_no_regrow() {
+        let mut v: MyVec<i32> = MyVec::with_capacity(16);
+        let buf = v.as_ptr();
+        // pushing up to the reserved capacity must not change the allocation
+        for i in 0..16 {
+            v.push(i);
+        }
+        let (ptr, len, cap) = v.into_raw_parts();
+        assert_eq!(ptr as *const i32, buf);
+        assert_eq!(len, 16);
+        assert!(cap >= 16);
+        drop(unsafe { MyVec::<i32>::from_raw_parts(ptr, len, cap) });
+    }
+
+    #[test]
+    fn test_reserve_single_allocation() {
+        let mut v: MyVec<i32> = MyVec::new();
+        v.push(1);
+        v.reserve(100);
+        let (ptr, len, cap) = v.into_raw_parts();
+        assert_eq!(len, 1);
+        assert!(cap >= 101);
+        drop(unsafe { MyVec::<i32>::from_raw_parts(ptr, len, cap) });
+    }
+
+    #[test]
+    fn test_raw_parts_round_trip() {
+        let mut v: MyVec<i32> = MyVec::new();
+        v.push(10);
+        v.push(20);
+        v.push(30);
+        let (ptr, len, cap) = v.into_raw_parts();
+        let v2 = unsafe { MyVec::<i32>::from_raw_parts(ptr, len, cap) };
+        assert_eq!(v2.len(), 3);
+        assert_eq!(v2[0], 10);
+        assert_eq!(v2[2], 30);
+    }
+
+    #[test]
+    fn test_drain_all() {
+        let mut v: MyVec<i32> = MyVec::new();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+        let drained: Vec<i32> = v.drain().collect();
+        assert_eq!(drained, vec![1, 2, 3]);
+        assert_eq!(v.len(), 0);
+        // the allocation is kept, so the vec is still usable
+        v.push(9);
+        assert_eq!(v[0], 9);
+    }
+
+    #[test]
+    fn test_drain_partial_drop() {
+        let mut v: MyVec<i32> = MyVec::new();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+        v.push(4);
+        {
+            let mut d = v.drain();
+            assert_eq!(d.next(), Some(1));
+            assert_eq!(d.next(), Some(2));
+            // dropping a half-consumed Drain must drop the remaining elements
+        }
+        assert_eq!(v.len(), 0);
+        v.push(5);
+        assert_eq!(v[0], 5);
+    }
+
+    #[test]
+    fn test_try_push_success() {
+        let mut v: MyVec<i32> = MyVec::new();
+        for i in 0..8 {
+            assert!(v.try_push(i).is_ok());
+        }
+        assert_eq!(v.len(), 8);
+        v.try_reserve(100).unwrap();
+        assert_eq!(v[7], 7);
+    }
+
+    #[test]
+    fn test_try_reserve_zst_is_noop() {
+        let mut v: MyVec<()> = MyVec::new();
+        v.push(());
+        assert_eq!(v.try_reserve(5), Ok(()));
+        assert_eq!(v.len(), 1);
+    }
+
+    #[test]
+    fn test_try_push_alloc_failure_returns_element() {
+        use allocator_api2::alloc::{AllocError, Allocator};
+        use std::alloc::Layout;
+        use std::ptr::NonNull;
+
+        struct FailAlloc;
+        unsafe impl Allocator for FailAlloc {
+            fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                Err(AllocError)
+            }
+            unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+        }
+
+        let mut v: MyVec<i32, FailAlloc> = MyVec::new_in(FailAlloc);
+        assert_eq!(v.try_push(7), Err(7));
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn test_dynvec_trait_objects() {
+        let mut v: DynVec<dyn std::fmt::Display> = DynVec::new();
+        v.push_unsized(1i32);
+        v.push_unsized("hello");
+        v.push_unsized(3.5f64);
+        assert_eq!(v.len(), 3);
+        assert_eq!(format!("{}", &v[0]), "1");
+        assert_eq!(format!("{}", &v[1]), "hello");
+        assert_eq!(format!("{}", &v[2]), "3.5");
+    }
+
+    #[test]
+    fn test_dynvec_slices_and_mutation() {
+        let mut v: DynVec<[u8]> = DynVec::new();
+        v.push_unsized([1u8, 2, 3]);
+        v.push_unsized([9u8, 8]);
+        assert_eq!(&v[0], &[1, 2, 3]);
+        assert_eq!(&v[1], &[9, 8]);
+        v[0][1] = 20;
+        assert_eq!(&v[0], &[1, 20, 3]);
+    }
+
+    #[test]
+    fn test_dynvec_drops_elements() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct D;
+        impl Drop for D {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        trait Marker {}
+        impl Marker for D {}
+
+        {
+            let mut v: DynVec<dyn Marker> = DynVec::new();
+            v.push_unsized(D);
+            v.push_unsized(D);
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_drain_double_ended() {
+        let mut v: MyVec<i32> = MyVec::new();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+        v.push(4);
+        let mut d = v.drain();
+        assert_eq!(d.next(), Some(1));
+        assert_eq!(d.next_back(), Some(4));
+        assert_eq!(d.next(), Some(2));
+        assert_eq!(d.next_back(), Some(3));
+        assert_eq!(d.next(), None);
+    }
 }